@@ -1,11 +1,16 @@
-use crate::bank::Bank;
-use csv::{ReaderBuilder, Trim};
+use crate::bank::{self, Bank, Transaction};
+use csv::{Reader, ReaderBuilder, Trim};
+use std::io;
+use std::sync::mpsc;
+use std::thread;
 use std::time::Instant;
 
 mod bank;
 
 fn main() {
-    let input_path = std::env::args().nth(1).expect("No input file!");
+    let args: Vec<String> = std::env::args().collect();
+    let input_path = args.get(1).expect("No input file!");
+    let workers = parse_workers(&args);
 
     let now = Instant::now();
 
@@ -17,12 +22,39 @@ fn main() {
         .from_path(input_path)
         .expect("Can't create csv reader!");
 
+    if workers <= 1 {
+        run_single_threaded(&mut rdr);
+    } else {
+        run_sharded(&mut rdr, workers);
+    }
+
+    eprintln!("Processed in: {} millis", now.elapsed().as_millis());
+}
+
+/// Parse the optional `--workers N` argument, falling back to a single thread.
+fn parse_workers(args: &[String]) -> usize {
+    args.iter()
+        .position(|arg| arg == "--workers")
+        .and_then(|index| args.get(index + 1))
+        .and_then(|value| value.parse::<usize>().ok())
+        .filter(|&count| count >= 1)
+        .unwrap_or(1)
+}
+
+/// Route a client to a worker. Hashing the client id (here, modulo the worker count) guarantees
+/// every transaction for a client lands on the same worker, so its channel keeps them in arrival
+/// (FIFO) order — a hard requirement, because a `dispute` must observe its `deposit`.
+fn shard(client: u16, workers: usize) -> usize {
+    client as usize % workers
+}
+
+/// Process every record serially in a single `Bank`.
+fn run_single_threaded<R: io::Read>(rdr: &mut Reader<R>) {
     let mut bank = Bank::default();
-    for record in rdr.deserialize() {
+    for record in rdr.deserialize::<Transaction>() {
         match record {
             Ok(transaction) => {
-                let processing_result = bank.process(transaction);
-                if let Err(error) = processing_result {
+                if let Err(error) = bank.process(transaction) {
                     eprintln!("Error occurred when processing transaction. {}.", error);
                 }
             }
@@ -32,6 +64,92 @@ fn main() {
         }
     }
     bank.write_accounts();
+    if let Err(error) = bank.audit() {
+        eprintln!("Audit failed. {}.", error);
+    }
+}
 
-    eprintln!("Processed in: {} millis", now.elapsed().as_millis());
+/// Process records concurrently across `workers` threads, each owning a disjoint shard of clients
+/// and its own input channel. The reader thread deserializes and dispatches by client; at
+/// end-of-stream each worker's shard is flushed into a single, order-insensitive writer.
+fn run_sharded<R: io::Read>(rdr: &mut Reader<R>, workers: usize) {
+    let mut senders = Vec::with_capacity(workers);
+    let mut handles = Vec::with_capacity(workers);
+    for _ in 0..workers {
+        let (sender, receiver) = mpsc::channel::<Transaction>();
+        senders.push(sender);
+        handles.push(thread::spawn(move || {
+            let mut bank = Bank::default();
+            for transaction in receiver {
+                if let Err(error) = bank.process(transaction) {
+                    eprintln!("Error occurred when processing transaction. {}.", error);
+                }
+            }
+            bank
+        }));
+    }
+
+    for record in rdr.deserialize::<Transaction>() {
+        match record {
+            Ok(transaction) => {
+                let worker = shard(transaction.client(), workers);
+                if senders[worker].send(transaction).is_err() {
+                    eprintln!("Worker {} stopped accepting transactions.", worker);
+                }
+            }
+            Err(error) => {
+                eprintln!("Can't deserialize transaction. Error: {:?}.", error);
+            }
+        }
+    }
+
+    // Dropping the senders closes every channel so the workers can drain and return their shard.
+    drop(senders);
+
+    let mut wtr = csv::WriterBuilder::new().from_writer(io::stdout());
+    if bank::write_header(&mut wtr).is_err() {
+        eprintln!("Can't write account headers!");
+    }
+    for handle in handles {
+        match handle.join() {
+            Ok(bank) => {
+                bank.write_rows(&mut wtr);
+                if let Err(error) = bank.audit() {
+                    eprintln!("Audit failed. {}.", error);
+                }
+            }
+            Err(_) => eprintln!("A worker thread panicked."),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_workers() {
+        assert_eq!(parse_workers(&["prog".into(), "in.csv".into()]), 1);
+        assert_eq!(
+            parse_workers(&["prog".into(), "in.csv".into(), "--workers".into(), "4".into()]),
+            4
+        );
+        // Zero or garbage falls back to single-threaded.
+        assert_eq!(
+            parse_workers(&["prog".into(), "in.csv".into(), "--workers".into(), "0".into()]),
+            1
+        );
+    }
+
+    #[test]
+    fn test_shard_keeps_client_on_one_worker() {
+        // Per-client FIFO relies on a client always hashing to the same worker, regardless of how
+        // many transactions it has.
+        let workers = 4;
+        let first = shard(42, workers);
+        for _ in 0..100 {
+            assert_eq!(shard(42, workers), first);
+        }
+        assert!(first < workers);
+    }
 }